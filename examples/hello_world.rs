@@ -1,4 +1,7 @@
-use photon::helpers::WgpuState;
+use photon::{
+	helpers::WgpuState,
+	scene::SceneEvent,
+};
 use winit::{
 	event::*,
 	event_loop::{
@@ -84,6 +87,18 @@ async fn run() {
 
 	println!("{:#?}", pixel_size);
 
+	// The scene isn't rendered yet, so these shapes are invisible hit regions.
+	let mut scene = photon::scene::Scene::new();
+	let mut hovered = None;
+	scene.insert_with_data(
+		photon::Shape::new((200.0, 100.0), (50.0, 50.0), [0.5, 0.2, 0.8, 1.0], 0.0, photon::ShapeType::Rectangle),
+		"rectangle",
+	);
+	scene.insert_with_data(
+		photon::Shape::new((0.0, 0.0), (300.0, 200.0), [0.2, 0.8, 0.5, 1.0], 50.0, photon::ShapeType::Circle),
+		"circle",
+	);
+
 	event_loop.run(move |event, _, control_flow| {
 		match event {
 			Event::WindowEvent { ref event, window_id } if window_id == window.id() => {
@@ -98,6 +113,24 @@ async fn run() {
 							},
 						..
 					} => *control_flow = ControlFlow::Exit,
+					WindowEvent::CursorMoved { .. }
+					| WindowEvent::CursorLeft { .. }
+					| WindowEvent::MouseInput { .. } => {
+						let hit = scene.dispatch_event::<&'static str, _>(event, |scene_event, id, name| {
+							match scene_event {
+								SceneEvent::CursorMoved if hovered != Some(id) => {
+									println!("Hovering {}", name);
+								}
+								SceneEvent::MouseInput { button, state } => {
+									println!("{:?} {:?} on {}", button, state, name);
+								}
+								_ => {}
+							}
+						});
+						if !matches!(event, WindowEvent::MouseInput { .. }) {
+							hovered = hit;
+						}
+					}
 					WindowEvent::Resized(_) => {
 						config.width = size.width.max(1);
 						config.height = size.height.max(1);
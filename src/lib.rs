@@ -1,4 +1,5 @@
 pub mod helpers;
+pub mod scene;
 
 use std::sync::Arc;
 
@@ -28,6 +29,7 @@ pub struct Pixel {
 }
 
 /// Enum representing different types of shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShapeType {
     /// Rectangle shape type.
     Rectangle,
@@ -104,6 +106,30 @@ impl Pixel {
 	}
 }
 
+impl From<ShapeType> for u32 {
+    /// Converts a `ShapeType` into the value stored in `Shape::shape_type`.
+    fn from(shape_type: ShapeType) -> Self {
+        match shape_type {
+            ShapeType::Rectangle => 0,
+            ShapeType::Circle => 1,
+        }
+    }
+}
+
+impl TryFrom<u32> for ShapeType {
+    /// The unknown value.
+    type Error = u32;
+
+    /// Converts a value from `Shape::shape_type` back into a `ShapeType`.
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ShapeType::Rectangle),
+            1 => Ok(ShapeType::Circle),
+            other => Err(other),
+        }
+    }
+}
+
 impl Shape {
     /// Creates a new instance of `Shape`.
     ///
@@ -139,15 +165,10 @@ impl Shape {
         radius: f32,
         shape_type: ShapeType,
     ) -> Self {
-        let shape_type_index = match shape_type {
-            ShapeType::Rectangle => 0,
-            ShapeType::Circle => 1,
-        };
-
         Self {
             size: [size.0, size.1],
             position: [position.0, position.1],
-            shape_type: shape_type_index,
+            shape_type: shape_type.into(),
             radius,
             color,
         }
@@ -242,3 +263,18 @@ impl Photon {
 		queue.submit(Some(encoder.finish()));
 	}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape_type_round_trip() {
+        for shape_type in [ShapeType::Rectangle, ShapeType::Circle] {
+            assert_eq!(ShapeType::try_from(u32::from(shape_type)), Ok(shape_type));
+        }
+        assert_eq!(u32::from(ShapeType::Rectangle), 0);
+        assert_eq!(u32::from(ShapeType::Circle), 1);
+        assert_eq!(ShapeType::try_from(2), Err(2));
+    }
+}
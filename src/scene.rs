@@ -0,0 +1,481 @@
+//! Scene nodes with user data and hit testing.
+//!
+//! Hit testing anchors every shape at its top-left corner: rectangles span
+//! from `position` to `position + size`, and circles are centered at
+//! `position + radius` on both axes, ignoring `size`.
+
+use std::any::Any;
+use std::fmt;
+
+use winit::event::{ElementState, MouseButton, WindowEvent};
+
+use crate::{Shape, ShapeType};
+
+/// Handle to a node inserted into a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+/// A shape in the scene together with optional user data.
+pub struct Node {
+	/// Shape belonging to this node.
+	pub shape: Shape,
+	/// Arbitrary data attached by the application.
+	data: Option<Box<dyn Any>>,
+}
+
+/// Event passed to the callback of [`Scene::dispatch_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneEvent {
+	/// The cursor moved over the node.
+	CursorMoved,
+	/// A mouse button was pressed or released over the node.
+	MouseInput {
+		/// Button that changed state.
+		button: MouseButton,
+		/// Whether the button was pressed or released.
+		state: ElementState,
+	},
+}
+
+/// Collection of nodes that can be hit tested.
+///
+/// Nodes are hit tested in insertion order, so later nodes sit on top of
+/// earlier ones. The scene isn't rendered by [`crate::Photon`] yet.
+#[derive(Default)]
+pub struct Scene {
+	nodes: Vec<Node>,
+	/// Last cursor position seen by [`Scene::dispatch_event`].
+	cursor: Option<(f32, f32)>,
+}
+
+impl Node {
+	/// Returns the attached data if it is of type `T`.
+	pub fn data<T: Any>(&self) -> Option<&T> {
+		self.data.as_ref()?.downcast_ref()
+	}
+
+	/// Returns the attached data mutably if it is of type `T`.
+	pub fn data_mut<T: Any>(&mut self) -> Option<&mut T> {
+		self.data.as_mut()?.downcast_mut()
+	}
+
+	/// Checks whether a point (in pixels) lies inside the node's shape.
+	///
+	/// Uses the anchoring described in the [module docs](self). Shapes with
+	/// an unknown `shape_type` never contain any point.
+	pub fn contains(&self, point: (f32, f32)) -> bool {
+		let [x, y] = self.shape.position;
+		match ShapeType::try_from(self.shape.shape_type) {
+			Ok(ShapeType::Rectangle) => {
+				let [w, h] = self.shape.size;
+				point.0 >= x && point.0 <= x + w && point.1 >= y && point.1 <= y + h
+			}
+			Ok(ShapeType::Circle) => {
+				let r = self.shape.radius;
+				let (dx, dy) = (point.0 - (x + r), point.1 - (y + r));
+				dx * dx + dy * dy <= r * r
+			}
+			Err(_) => false,
+		}
+	}
+}
+
+impl fmt::Debug for Node {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Node")
+			.field("shape", &self.shape)
+			.field("has_data", &self.data.is_some())
+			.finish()
+	}
+}
+
+impl fmt::Debug for Scene {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Scene")
+			.field("nodes", &self.nodes)
+			.field("cursor", &self.cursor)
+			.finish()
+	}
+}
+
+impl Scene {
+	/// Creates an empty `Scene`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Inserts a shape without any user data.
+	///
+	/// # Returns
+	///
+	/// The `NodeId` of the new node.
+	pub fn insert(&mut self, shape: Shape) -> NodeId {
+		self.push(Node { shape, data: None })
+	}
+
+	/// Inserts a shape with arbitrary user data attached to it.
+	///
+	/// The data can later be retrieved with [`Scene::data`] or [`Node::data`],
+	/// for example after a [`Scene::hit_test`] or inside [`Scene::dispatch`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use photon::{scene::Scene, Shape, ShapeType};
+	///
+	/// struct Button { name: &'static str }
+	///
+	/// let mut scene = Scene::new();
+	/// let shape = Shape::new((100.0, 50.0), (0.0, 0.0), [1.0; 4], 0.0, ShapeType::Rectangle);
+	/// scene.insert_with_data(shape, Button { name: "ok" });
+	///
+	/// let hit = scene.hit_test((10.0, 10.0)).unwrap();
+	/// assert_eq!(scene.data::<Button>(hit).unwrap().name, "ok");
+	/// ```
+	pub fn insert_with_data<T: Any>(&mut self, shape: Shape, data: T) -> NodeId {
+		self.push(Node { shape, data: Some(Box::new(data)) })
+	}
+
+	/// Returns the node for the given id.
+	pub fn get(&self, id: NodeId) -> Option<&Node> {
+		self.nodes.get(id.0)
+	}
+
+	/// Returns the node for the given id mutably.
+	pub fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+		self.nodes.get_mut(id.0)
+	}
+
+	/// Returns the data attached to a node if it is of type `T`.
+	pub fn data<T: Any>(&self, id: NodeId) -> Option<&T> {
+		self.get(id)?.data()
+	}
+
+	/// Returns the data attached to a node mutably if it is of type `T`.
+	pub fn data_mut<T: Any>(&mut self, id: NodeId) -> Option<&mut T> {
+		self.get_mut(id)?.data_mut()
+	}
+
+	/// Finds the topmost node containing a point (in pixels).
+	pub fn hit_test(&self, point: (f32, f32)) -> Option<NodeId> {
+		self.nodes.iter().rposition(|node| node.contains(point)).map(NodeId)
+	}
+
+	/// Calls `callback` with the topmost node containing a point (in pixels).
+	///
+	/// # Returns
+	///
+	/// The `NodeId` of the node that was hit, if any.
+	pub fn dispatch<F>(&mut self, point: (f32, f32), callback: F) -> Option<NodeId>
+	where
+		F: FnOnce(NodeId, &mut Node),
+	{
+		let id = self.hit_test(point)?;
+		callback(id, &mut self.nodes[id.0]);
+		Some(id)
+	}
+
+	/// Calls `callback` with the data of the topmost node containing a point,
+	/// if that data is of type `T`.
+	///
+	/// # Returns
+	///
+	/// The `NodeId` the callback was called for, if any.
+	pub fn dispatch_data<T, F>(&mut self, point: (f32, f32), callback: F) -> Option<NodeId>
+	where
+		T: Any,
+		F: FnOnce(NodeId, &mut T),
+	{
+		let id = self.hit_test(point)?;
+		callback(id, self.data_mut::<T>(id)?);
+		Some(id)
+	}
+
+	/// Forwards a winit mouse event to [`Scene::dispatch_data`].
+	///
+	/// `WindowEvent::CursorMoved` is dispatched at the new cursor position,
+	/// which is also remembered so that a following `WindowEvent::MouseInput`
+	/// is dispatched there. `WindowEvent::CursorLeft` forgets the position,
+	/// and all other events are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The window event received from the event loop.
+	/// * `callback` - Called with the event kind, the hit node's id and its data.
+	///
+	/// # Returns
+	///
+	/// The `NodeId` the callback was called for, if any.
+	pub fn dispatch_event<T, F>(&mut self, event: &WindowEvent, callback: F) -> Option<NodeId>
+	where
+		T: Any,
+		F: FnOnce(SceneEvent, NodeId, &mut T),
+	{
+		match event {
+			WindowEvent::CursorMoved { position, .. } => {
+				let point = (position.x as f32, position.y as f32);
+				self.cursor = Some(point);
+				self.dispatch_data(point, |id, data| callback(SceneEvent::CursorMoved, id, data))
+			}
+			WindowEvent::MouseInput { button, state, .. } => {
+				let event = SceneEvent::MouseInput { button: *button, state: *state };
+				self.dispatch_data(self.cursor?, |id, data| callback(event, id, data))
+			}
+			WindowEvent::CursorLeft { .. } => {
+				self.cursor = None;
+				None
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns the last cursor position seen by [`Scene::dispatch_event`].
+	pub fn cursor(&self) -> Option<(f32, f32)> {
+		self.cursor
+	}
+
+	/// Iterates over all nodes in insertion order.
+	pub fn iter(&self) -> impl Iterator<Item = (NodeId, &Node)> {
+		self.nodes.iter().enumerate().map(|(i, node)| (NodeId(i), node))
+	}
+
+	fn push(&mut self, node: Node) -> NodeId {
+		self.nodes.push(node);
+		NodeId(self.nodes.len() - 1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use winit::dpi::PhysicalPosition;
+	use winit::event::{DeviceId, ModifiersState};
+
+	fn rect(position: (f32, f32), size: (f32, f32)) -> Shape {
+		Shape::new(size, position, [1.0; 4], 0.0, ShapeType::Rectangle)
+	}
+
+	fn circle(position: (f32, f32), radius: f32) -> Shape {
+		Shape::new((0.0, 0.0), position, [1.0; 4], radius, ShapeType::Circle)
+	}
+
+	fn node(shape: Shape) -> Node {
+		Node { shape, data: None }
+	}
+
+	#[test]
+	fn rectangle_contains() {
+		let node = node(rect((10.0, 20.0), (100.0, 50.0)));
+		assert!(node.contains((50.0, 40.0)));
+		assert!(node.contains((10.0, 20.0)));
+		assert!(node.contains((110.0, 70.0)));
+		assert!(node.contains((10.0, 70.0)));
+		assert!(node.contains((110.0, 20.0)));
+		assert!(node.contains((60.0, 20.0)));
+		assert!(!node.contains((9.9, 40.0)));
+		assert!(!node.contains((110.1, 40.0)));
+		assert!(!node.contains((50.0, 19.9)));
+		assert!(!node.contains((50.0, 70.1)));
+	}
+
+	#[test]
+	fn circle_contains() {
+		// Centered at (20, 20).
+		let node = node(circle((10.0, 10.0), 10.0));
+		assert!(node.contains((20.0, 20.0)));
+		assert!(node.contains((29.9, 20.0)));
+		assert!(node.contains((30.0, 20.0)));
+		assert!(!node.contains((30.1, 20.0)));
+		assert!(!node.contains((20.0, 9.9)));
+		// Inside the bounding box but outside the circle.
+		assert!(!node.contains((11.0, 11.0)));
+	}
+
+	#[test]
+	fn unknown_shape_type_contains_nothing() {
+		let mut shape = rect((0.0, 0.0), (10.0, 10.0));
+		shape.shape_type = 42;
+		assert!(!node(shape).contains((5.0, 5.0)));
+	}
+
+	#[test]
+	fn hit_test_returns_topmost() {
+		let mut scene = Scene::new();
+		let bottom = scene.insert(rect((0.0, 0.0), (100.0, 100.0)));
+		let top = scene.insert(rect((50.0, 50.0), (100.0, 100.0)));
+		assert_eq!(scene.hit_test((75.0, 75.0)), Some(top));
+		assert_eq!(scene.hit_test((25.0, 25.0)), Some(bottom));
+	}
+
+	#[test]
+	fn hit_test_miss() {
+		let mut scene = Scene::new();
+		assert_eq!(scene.hit_test((0.0, 0.0)), None);
+		scene.insert(rect((0.0, 0.0), (10.0, 10.0)));
+		assert_eq!(scene.hit_test((50.0, 50.0)), None);
+	}
+
+	#[test]
+	fn data_wrong_type() {
+		let mut scene = Scene::new();
+		let id = scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 5u32);
+		assert_eq!(scene.data::<u32>(id), Some(&5));
+		assert_eq!(scene.data::<i32>(id), None);
+		assert_eq!(scene.data::<String>(id), None);
+	}
+
+	#[test]
+	fn data_without_data() {
+		let mut scene = Scene::new();
+		let id = scene.insert(rect((0.0, 0.0), (10.0, 10.0)));
+		assert_eq!(scene.data::<u32>(id), None);
+		assert_eq!(scene.get(id).unwrap().data::<u32>(), None);
+	}
+
+	#[test]
+	fn data_mut_is_visible() {
+		let mut scene = Scene::new();
+		let id = scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), String::from("a"));
+		scene.data_mut::<String>(id).unwrap().push('b');
+		assert_eq!(scene.data::<String>(id).map(String::as_str), Some("ab"));
+	}
+
+	#[test]
+	fn out_of_range_id() {
+		let mut other = Scene::new();
+		other.insert(rect((0.0, 0.0), (10.0, 10.0)));
+		let stale = other.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 1u32);
+
+		let mut scene = Scene::new();
+		assert!(scene.get(stale).is_none());
+		assert!(scene.get_mut(stale).is_none());
+		assert_eq!(scene.data::<u32>(stale), None);
+		assert_eq!(scene.data_mut::<u32>(stale), None);
+	}
+
+	#[test]
+	fn dispatch_calls_callback() {
+		let mut scene = Scene::new();
+		scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 1u32);
+		let top = scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 2u32);
+
+		let mut seen = None;
+		let hit = scene.dispatch_data::<u32, _>((5.0, 5.0), |id, value| {
+			*value += 10;
+			seen = Some(id);
+		});
+		assert_eq!(hit, Some(top));
+		assert_eq!(seen, Some(top));
+		assert_eq!(scene.data::<u32>(top), Some(&12));
+
+		assert_eq!(scene.dispatch((50.0, 50.0), |_, _| panic!("no node hit")), None);
+		assert_eq!(scene.dispatch_data::<String, _>((5.0, 5.0), |_, _| panic!("wrong type")), None);
+	}
+
+	#[test]
+	fn dispatch_hit_gets_node() {
+		let mut scene = Scene::new();
+		scene.insert(rect((0.0, 0.0), (10.0, 10.0)));
+		let top = scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 1u32);
+
+		let hit = scene.dispatch((5.0, 5.0), |id, node| {
+			assert_eq!(id, top);
+			*node.data_mut::<u32>().unwrap() = 7;
+			node.shape.color = [0.0; 4];
+		});
+		assert_eq!(hit, Some(top));
+		assert_eq!(scene.data::<u32>(top), Some(&7));
+		assert_eq!(scene.get(top).unwrap().shape.color, [0.0; 4]);
+	}
+
+	#[allow(deprecated)]
+	fn cursor_moved(x: f64, y: f64) -> WindowEvent<'static> {
+		WindowEvent::CursorMoved {
+			device_id: unsafe { DeviceId::dummy() },
+			position: PhysicalPosition::new(x, y),
+			modifiers: ModifiersState::empty(),
+		}
+	}
+
+	#[allow(deprecated)]
+	fn mouse_input(state: ElementState) -> WindowEvent<'static> {
+		WindowEvent::MouseInput {
+			device_id: unsafe { DeviceId::dummy() },
+			state,
+			button: MouseButton::Left,
+			modifiers: ModifiersState::empty(),
+		}
+	}
+
+	#[test]
+	fn dispatch_event_cursor_moved() {
+		let mut scene = Scene::new();
+		scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), "bottom");
+		let top = scene.insert_with_data(rect((5.0, 5.0), (10.0, 10.0)), "top");
+
+		let mut seen = None;
+		let hit = scene.dispatch_event::<&'static str, _>(&cursor_moved(7.5, 7.5), |event, id, name| {
+			seen = Some((event, id, *name));
+		});
+		assert_eq!(hit, Some(top));
+		assert_eq!(seen, Some((SceneEvent::CursorMoved, top, "top")));
+		assert_eq!(scene.cursor(), Some((7.5, 7.5)));
+
+		let miss = scene.dispatch_event::<&'static str, _>(&cursor_moved(50.0, 50.0), |_, _, _| {
+			panic!("no node hit")
+		});
+		assert_eq!(miss, None);
+		assert_eq!(scene.cursor(), Some((50.0, 50.0)));
+	}
+
+	#[test]
+	fn dispatch_event_mouse_input() {
+		let mut scene = Scene::new();
+		let id = scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 0u32);
+
+		// No cursor position known yet.
+		let click = mouse_input(ElementState::Pressed);
+		assert_eq!(scene.dispatch_event::<u32, _>(&click, |_, _, _| panic!("no cursor")), None);
+
+		scene.dispatch_event::<u32, _>(&cursor_moved(5.0, 5.0), |_, _, _| {});
+		let mut seen = None;
+		let hit = scene.dispatch_event::<u32, _>(&click, |event, id, clicks| {
+			*clicks += 1;
+			seen = Some(event);
+		});
+		assert_eq!(hit, Some(id));
+		assert_eq!(
+			seen,
+			Some(SceneEvent::MouseInput { button: MouseButton::Left, state: ElementState::Pressed })
+		);
+		assert_eq!(scene.data::<u32>(id), Some(&1));
+	}
+
+	#[test]
+	fn dispatch_event_ignores_other_events() {
+		let mut scene = Scene::new();
+		scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), 0u32);
+		scene.dispatch_event::<u32, _>(&cursor_moved(5.0, 5.0), |_, _, _| {});
+
+		let hit = scene.dispatch_event::<u32, _>(&WindowEvent::Focused(true), |_, _, _| {
+			panic!("event should be ignored")
+		});
+		assert_eq!(hit, None);
+
+		let left = WindowEvent::CursorLeft { device_id: unsafe { DeviceId::dummy() } };
+		assert_eq!(scene.dispatch_event::<u32, _>(&left, |_, _, _| panic!("cursor left")), None);
+		assert_eq!(scene.cursor(), None);
+	}
+
+	#[test]
+	fn data_without_send() {
+		use std::cell::RefCell;
+		use std::rc::Rc;
+
+		let model = Rc::new(RefCell::new(0));
+		let mut scene = Scene::new();
+		let id = scene.insert_with_data(rect((0.0, 0.0), (10.0, 10.0)), model.clone());
+		*scene.data::<Rc<RefCell<i32>>>(id).unwrap().borrow_mut() = 3;
+		assert_eq!(*model.borrow(), 3);
+	}
+}